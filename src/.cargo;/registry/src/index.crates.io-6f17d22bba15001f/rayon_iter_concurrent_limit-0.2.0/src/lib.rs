@@ -108,6 +108,12 @@
 
 use rayon::iter::{Chunks, IndexedParallelIterator};
 
+/// Re-exported so the `map` arm of [`iter_concurrent_limit_balanced`] can expand to
+/// `$crate::Either` in the caller's crate without requiring that crate to depend on `either`
+/// itself. `rayon` implements `ParallelIterator` for `either::Either<L, R>` (not a `rayon::iter`
+/// type of its own; `rayon::iter::Either` is a re-export of this same type).
+pub use either::Either;
+
 /// Subdivide a [`rayon::iter::IndexedParallelIterator`] into `num_chunks` chunks.
 ///
 /// This returns the output of the [`IndexedParallelIterator::chunks`] function with a chunk size calculated according to:
@@ -128,11 +134,79 @@ pub fn iter_subdivide<I: IndexedParallelIterator>(num_chunks: usize, iterator: I
     if num_chunks == 0 {
         iterator.chunks(1)
     } else {
-        let chunk_size = std::cmp::max((iterator.len() + num_chunks - 1) / num_chunks, 1);
+        let chunk_size = chunk_size_for(iterator.len(), num_chunks);
         iterator.chunks(chunk_size)
     }
 }
 
+/// The chunk size [`iter_subdivide`] uses to split `len` items into (at most) `num_chunks` chunks.
+///
+/// `pub` (rather than crate-private) because the `position_first` arm of [`iter_concurrent_limit`]
+/// expands to a reference to `$crate::chunk_size_for` in the caller's crate, to recover a global
+/// index from a `(chunk_index, local_index)` pair by reconstructing the same chunk boundaries.
+pub fn chunk_size_for(len: usize, num_chunks: usize) -> usize {
+    std::cmp::max((len + num_chunks - 1) / num_chunks, 1)
+}
+
+/// A budget of "weight" shared between concurrent workers, used by the `weighted` arms of
+/// [`iter_concurrent_limit`] to bound total in-flight *work* rather than item count.
+///
+/// Unlike chunking the iterator into `concurrent_limit` pieces, this lets an arbitrary number of
+/// items run concurrently as long as their combined weight (as computed by a `weight_fn`) fits
+/// within `budget`. A single item whose weight exceeds `budget` is still allowed to proceed once no
+/// other item is in flight, guaranteeing forward progress instead of deadlocking.
+pub struct WeightedPermits {
+    budget: usize,
+    consumed: std::sync::Mutex<usize>,
+    condvar: std::sync::Condvar,
+}
+
+impl WeightedPermits {
+    /// Create a new [`WeightedPermits`] with the given total `budget`.
+    #[must_use]
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            consumed: std::sync::Mutex::new(0),
+            condvar: std::sync::Condvar::new(),
+        }
+    }
+
+    /// Block until `weight` can be added to the consumed budget, then do so and return a guard
+    /// that subtracts it again on drop.
+    ///
+    /// If no other weight is currently consumed, this proceeds immediately even if `weight` alone
+    /// exceeds `budget`, so that a single oversized item cannot deadlock the budget.
+    #[must_use]
+    pub fn acquire(&self, weight: usize) -> WeightedPermit<'_> {
+        let mut consumed = self.consumed.lock().unwrap();
+        while *consumed != 0 && *consumed + weight > self.budget {
+            consumed = self.condvar.wait(consumed).unwrap();
+        }
+        *consumed += weight;
+        WeightedPermit {
+            permits: self,
+            weight,
+        }
+    }
+}
+
+/// A held portion of a [`WeightedPermits`] budget, released back on drop.
+#[must_use]
+pub struct WeightedPermit<'a> {
+    permits: &'a WeightedPermits,
+    weight: usize,
+}
+
+impl Drop for WeightedPermit<'_> {
+    fn drop(&mut self) {
+        let mut consumed = self.permits.consumed.lock().unwrap();
+        *consumed -= self.weight;
+        drop(consumed);
+        self.permits.condvar.notify_all();
+    }
+}
+
 // TODO: Support more methods
 /// Apply a method on a [`rayon::iter::IndexedParallelIterator`] with a limit on the number of concurrent executions of the function passed to the method.
 ///
@@ -180,6 +254,21 @@ pub fn iter_subdivide<I: IndexedParallelIterator>(num_chunks: usize, iterator: I
 /// # }
 /// ```
 ///
+/// ### for_each_init / for_each_with
+/// `for_each_init` calls `init` once per chunk to create per-chunk scratch state (an RNG, a reusable
+/// decode buffer, ...), giving at most `concurrent_limit` live states instead of one per item.
+/// `for_each_with` is the same but clones a seed value instead of calling an initialiser.
+/// ```rust
+/// # use rayon::iter::{IntoParallelIterator, ParallelIterator};
+/// # use rayon_iter_concurrent_limit::iter_concurrent_limit;
+/// let op = |buf: &mut Vec<usize>, i: usize| {
+///     buf.clear();
+///     buf.push(i);
+/// };
+/// iter_concurrent_limit!(2, (0..10), for_each_init, Vec::new, op);
+/// iter_concurrent_limit!(2, (0..10), for_each_with, Vec::<usize>::new(), op);
+/// ```
+///
 /// ### map
 /// ```rust
 /// # use rayon::iter::{IntoParallelIterator, ParallelIterator};
@@ -194,6 +283,19 @@ pub fn iter_subdivide<I: IndexedParallelIterator>(num_chunks: usize, iterator: I
 /// assert_eq!(sum, (0..100).into_iter().map(op).sum::<usize>());
 /// ```
 ///
+/// ### map_init / map_with
+/// Like `for_each_init`/`for_each_with`, but for `map`: `init`/`seed.clone()` runs once per chunk.
+/// ```rust
+/// # use rayon::iter::{IntoParallelIterator, ParallelIterator};
+/// # use rayon_iter_concurrent_limit::iter_concurrent_limit;
+/// let op = |buf: &mut Vec<usize>, i: usize| -> usize {
+///     buf.push(i);
+///     buf.len()
+/// };
+/// let counts = iter_concurrent_limit!(2, (0..10), map_init, Vec::new, op).collect::<Vec<_>>();
+/// assert_eq!(counts.len(), 10);
+/// ```
+///
 /// ### filter
 /// ```rust
 /// # use rayon::iter::{IntoParallelIterator, ParallelIterator};
@@ -246,6 +348,74 @@ pub fn iter_subdivide<I: IndexedParallelIterator>(num_chunks: usize, iterator: I
 /// assert_eq!(all_eq_50, (0..100).into_iter().all(op));
 /// ```
 ///
+/// ### find_first / find_map_first / position_first
+/// `find_any`/`find_map_any` let any matching chunk win; `find_first`/`find_map_first`/
+/// `position_first` instead report the match from the earliest chunk, letting rayon cancel later
+/// chunks once it's confirmed.
+/// ```rust
+/// # use rayon::iter::{IntoParallelIterator, ParallelIterator};
+/// # use rayon_iter_concurrent_limit::iter_concurrent_limit;
+/// let predicate = |i: &usize| -> bool {
+///     // ... do work with limited concurrency
+///     *i == 50
+/// };
+/// let found = iter_concurrent_limit!(2, (0..100), find_first, predicate);
+/// assert_eq!(found, Some(50));
+/// // `position_first` takes each item by value, like `Iterator::position`.
+/// let position_predicate = |i: usize| -> bool { i == 50 };
+/// let position = iter_concurrent_limit!(2, (0..100), position_first, position_predicate);
+/// assert_eq!(position, Some(50));
+/// ```
+///
+/// ### map with weighted permits
+/// A flat `concurrent_limit` bounds the number of items running concurrently, which is the wrong
+/// budget when items cost wildly different amounts of work (e.g. decoding compressed chunks of
+/// very different sizes). The `weighted` arms take a `weight_fn` and bound the total in-flight
+/// weight instead, via [`WeightedPermits`].
+/// ```rust
+/// # use rayon::iter::{IntoParallelIterator, ParallelIterator};
+/// # use rayon_iter_concurrent_limit::iter_concurrent_limit;
+/// let weight_fn = |i: &usize| -> usize { *i + 1 };
+/// let op = |i: usize| -> usize { i * 2 };
+/// let output = iter_concurrent_limit!(10, (0..100), map, weighted, weight_fn, op)
+///     .collect::<Vec<usize>>();
+/// assert_eq!(output, (0..100).into_iter().map(op).collect::<Vec<usize>>());
+/// ```
+///
+/// ### reduce
+/// `reduce` runs a streaming reduction instead of collecting into a `Vec` first, so the low-memory
+/// benefit of limiting concurrency is not lost to an intermediate allocation. `identity` seeds
+/// both the fold within each chunk and the combination of per-chunk accumulators, so `op` must be
+/// associative. An empty iterator yields `identity()`.
+/// ```rust
+/// # use rayon::iter::{IntoParallelIterator, ParallelIterator};
+/// # use rayon_iter_concurrent_limit::iter_concurrent_limit;
+/// let sum = iter_concurrent_limit!(2, (0..100), reduce, || 0usize, |a: usize, b: usize| a + b);
+/// assert_eq!(sum, (0..100).sum::<usize>());
+/// ```
+///
+/// ### fold
+/// `fold` only folds within each chunk, yielding a parallel iterator of the (at most
+/// `concurrent_limit`) per-chunk accumulators, which the caller then combines however it likes.
+/// ```rust
+/// # use rayon::iter::{IntoParallelIterator, ParallelIterator};
+/// # use rayon_iter_concurrent_limit::iter_concurrent_limit;
+/// let sum = iter_concurrent_limit!(2, (0..100), fold, || 0usize, |a: usize, b: usize| a + b)
+///     .sum::<usize>();
+/// assert_eq!(sum, (0..100).sum::<usize>());
+/// ```
+///
+/// ### try_fold
+/// `try_fold` short-circuits: once any chunk's fold returns `Err`, no further chunks are combined.
+/// ```rust
+/// # use rayon::iter::{IntoParallelIterator, ParallelIterator};
+/// # use rayon_iter_concurrent_limit::iter_concurrent_limit;
+/// let result = iter_concurrent_limit!(2, (0..100), try_fold, || 0usize, |a: usize, b: usize| {
+///     if b == 50 { Err("hit 50") } else { Ok(a + b) }
+/// });
+/// assert_eq!(result, Err("hit 50"));
+/// ```
+///
 #[macro_export]
 macro_rules! iter_concurrent_limit {
     ( $concurrent_limit:expr, $iterator:expr, for_each, $op:expr ) => {{
@@ -260,8 +430,28 @@ macro_rules! iter_concurrent_limit {
             chunks.for_each(|chunk| chunk.into_iter().for_each(op))
         }
     }};
-    // TODO: for_each_with?
-    // TODO: for_each_init?
+    // `for_each_with`/`for_each_init` give the intended per-chunk-initialisation semantics that a
+    // plain thread-local-init method (e.g. `ParallelIterator::for_each_init`) does not have when run
+    // through this macro: `$init`/`$seed.clone()` runs once per chunk, giving at most
+    // `concurrent_limit` live states, rather than once per rayon-internal split.
+    ( $concurrent_limit:expr, $iterator:expr, for_each_with, $seed:expr, $op:expr ) => {{
+        let seed = $seed;
+        let op = $op;
+        let chunks = $crate::iter_subdivide($concurrent_limit, $iterator.into_par_iter());
+        chunks.for_each(move |chunk| {
+            let mut state = seed.clone();
+            chunk.into_iter().for_each(|item| op(&mut state, item))
+        })
+    }};
+    ( $concurrent_limit:expr, $iterator:expr, for_each_init, $init:expr, $op:expr ) => {{
+        let init = $init;
+        let op = $op;
+        let chunks = $crate::iter_subdivide($concurrent_limit, $iterator.into_par_iter());
+        chunks.for_each(move |chunk| {
+            let mut state = init();
+            chunk.into_iter().for_each(|item| op(&mut state, item))
+        })
+    }};
     ( $concurrent_limit:expr, $iterator:expr, try_for_each, $op:expr ) => {{
         let concurrent_limit = $concurrent_limit;
         let op = $op;
@@ -280,8 +470,49 @@ macro_rules! iter_concurrent_limit {
         let chunks = $crate::iter_subdivide($concurrent_limit, $iterator.into_par_iter());
         chunks.flat_map_iter(|chunk| chunk.into_iter().map($map_op))
     }};
-    // TODO: map_with?
-    // TODO: map_init?
+    // `map_with`/`map_init` run `$seed.clone()`/`$init()` once per chunk, giving at most
+    // `concurrent_limit` live per-worker states (RNGs, reusable decode buffers, ...) instead of
+    // silently diverging from `ParallelIterator::map_with`/`map_init`'s semantics.
+    // `map_op` is shared via `Arc` (rather than moved) because the chunk closure below is called
+    // once per chunk but must hand an *owned* copy of it to each chunk's returned iterator.
+    ( $concurrent_limit:expr, $iterator:expr, map_with, $seed:expr, $map_op:expr ) => {{
+        let seed = $seed;
+        let map_op = std::sync::Arc::new($map_op);
+        let chunks = $crate::iter_subdivide($concurrent_limit, $iterator.into_par_iter());
+        chunks.flat_map_iter(move |chunk| {
+            let mut state = seed.clone();
+            let map_op = std::sync::Arc::clone(&map_op);
+            chunk.into_iter().map(move |item| map_op(&mut state, item))
+        })
+    }};
+    ( $concurrent_limit:expr, $iterator:expr, map_init, $init:expr, $map_op:expr ) => {{
+        let init = $init;
+        let map_op = std::sync::Arc::new($map_op);
+        let chunks = $crate::iter_subdivide($concurrent_limit, $iterator.into_par_iter());
+        chunks.flat_map_iter(move |chunk| {
+            let mut state = init();
+            let map_op = std::sync::Arc::clone(&map_op);
+            chunk.into_iter().map(move |item| map_op(&mut state, item))
+        })
+    }};
+    ( $budget:expr, $iterator:expr, for_each, weighted, $weight_fn:expr, $op:expr ) => {{
+        let permits = $crate::WeightedPermits::new($budget);
+        let weight_fn = $weight_fn;
+        let op = $op;
+        $iterator.into_par_iter().for_each(|item| {
+            let _permit = permits.acquire(weight_fn(&item));
+            op(item)
+        })
+    }};
+    ( $budget:expr, $iterator:expr, map, weighted, $weight_fn:expr, $op:expr ) => {{
+        let permits = $crate::WeightedPermits::new($budget);
+        let weight_fn = $weight_fn;
+        let op = $op;
+        $iterator.into_par_iter().map(move |item| {
+            let _permit = permits.acquire(weight_fn(&item));
+            op(item)
+        })
+    }};
     // IGNORE: inspect
     ( $concurrent_limit:expr, $iterator:expr, update, $update_op:expr ) => {{
         let chunks = $crate::iter_subdivide($concurrent_limit, $iterator.into_par_iter());
@@ -306,13 +537,56 @@ macro_rules! iter_concurrent_limit {
     //     chunks.flat_map_iter(|chunk| chunk.into_iter().map($map_op))
     // }};
     // TODO: flat_map_iter?
-    // TODO: reduce?
+    // `reduce` folds each chunk sequentially with `$op` (seeded by `$identity`), then combines the
+    // at-most-`concurrent_limit` per-chunk accumulators with `$op` in parallel. `$op` must be
+    // associative. An empty iterator yields `identity()`.
+    ( $concurrent_limit:expr, $iterator:expr, reduce, $identity:expr, $op:expr ) => {{
+        let concurrent_limit = $concurrent_limit;
+        let identity = $identity;
+        let op = $op;
+        if concurrent_limit == 0 {
+            $iterator.into_par_iter().reduce(identity, op)
+        } else {
+            let chunks = $crate::iter_subdivide(concurrent_limit, $iterator.into_par_iter());
+            chunks
+                .map(|chunk| chunk.into_iter().fold(identity(), &op))
+                .reduce(identity, op)
+        }
+    }};
+    // `fold` folds each chunk sequentially with `$fold_op` (seeded by `$identity`), yielding a
+    // parallel iterator of the at-most-`concurrent_limit` per-chunk accumulators for the caller to
+    // combine however it likes (e.g. with a further `.reduce`).
+    ( $concurrent_limit:expr, $iterator:expr, fold, $identity:expr, $fold_op:expr ) => {{
+        let concurrent_limit = $concurrent_limit;
+        let identity = $identity;
+        let fold_op = $fold_op;
+        if concurrent_limit == 0 {
+            $iterator.into_par_iter().fold(identity, fold_op)
+        } else {
+            let chunks = $crate::iter_subdivide(concurrent_limit, $iterator.into_par_iter());
+            chunks.map(move |chunk| chunk.into_iter().fold(identity(), &fold_op))
+        }
+    }};
     // TODO: reduce_with?
     // TODO: try_reduce?
     // TODO: try_reduce_with?
-    // TODO: fold?
     // TODO: fold_with?
-    // TODO: try_fold?
+    ( $concurrent_limit:expr, $iterator:expr, try_fold, $identity:expr, $op:expr ) => {{
+        let concurrent_limit = $concurrent_limit;
+        let identity = $identity;
+        let op = $op;
+        if concurrent_limit == 0 {
+            $iterator
+                .into_par_iter()
+                .try_fold(identity, &op)
+                .try_reduce(identity, op)
+        } else {
+            let chunks = $crate::iter_subdivide(concurrent_limit, $iterator.into_par_iter());
+            chunks
+                .map(|chunk| chunk.into_iter().try_fold(identity(), &op))
+                .try_reduce(identity, op)
+        }
+    }};
     // TODO: try_fold_with?
     // ( $concurrent_limit:expr, $iterator:expr, max_by_key, $f:expr ) => {{
     //     let chunks = $crate::iter_subdivide($concurrent_limit, $iterator.into_par_iter());
@@ -326,12 +600,88 @@ macro_rules! iter_concurrent_limit {
     //         .flat_map(|chunk| chunk.into_iter().min_by_key($f))
     //         .min_by_key($f)
     // }};
-    // TODO: find_any?
-    // TODO: find_first?
+    // `find_any`/`find_map_any` let any chunk's match terminate the search immediately, with no
+    // preference for which one is reported first.
+    ( $concurrent_limit:expr, $iterator:expr, find_any, $predicate:expr ) => {{
+        let concurrent_limit = $concurrent_limit;
+        let predicate = $predicate;
+        if concurrent_limit == 0 {
+            $iterator.into_par_iter().find_any(predicate)
+        } else if concurrent_limit == 1 {
+            $iterator.into_iter().find(predicate)
+        } else {
+            let chunks = $crate::iter_subdivide(concurrent_limit, $iterator.into_par_iter());
+            chunks.find_map_any(|chunk| chunk.into_iter().find(predicate))
+        }
+    }};
+    ( $concurrent_limit:expr, $iterator:expr, find_map_any, $f:expr ) => {{
+        let concurrent_limit = $concurrent_limit;
+        let f = $f;
+        if concurrent_limit == 0 {
+            $iterator.into_par_iter().find_map_any(f)
+        } else if concurrent_limit == 1 {
+            $iterator.into_iter().find_map(f)
+        } else {
+            let chunks = $crate::iter_subdivide(concurrent_limit, $iterator.into_par_iter());
+            chunks.find_map_any(|chunk| chunk.into_iter().find_map(f))
+        }
+    }};
+    // `find_first`/`find_map_first`/`position_first` scan each chunk sequentially, then rely on
+    // [`rayon::iter::ParallelIterator::find_map_first`]'s own ordered short-circuiting (chunks are
+    // produced in order by `iter_subdivide`) to report the match from the earliest chunk, and
+    // cancel chunks after it once that match is confirmed.
+    ( $concurrent_limit:expr, $iterator:expr, find_first, $predicate:expr ) => {{
+        let concurrent_limit = $concurrent_limit;
+        let predicate = $predicate;
+        if concurrent_limit == 0 {
+            $iterator.into_par_iter().find_first(predicate)
+        } else if concurrent_limit == 1 {
+            $iterator.into_iter().find(predicate)
+        } else {
+            let chunks = $crate::iter_subdivide(concurrent_limit, $iterator.into_par_iter());
+            chunks.find_map_first(|chunk| chunk.into_iter().find(predicate))
+        }
+    }};
+    ( $concurrent_limit:expr, $iterator:expr, find_map_first, $f:expr ) => {{
+        let concurrent_limit = $concurrent_limit;
+        let f = $f;
+        if concurrent_limit == 0 {
+            $iterator.into_par_iter().find_map_first(f)
+        } else if concurrent_limit == 1 {
+            $iterator.into_iter().find_map(f)
+        } else {
+            let chunks = $crate::iter_subdivide(concurrent_limit, $iterator.into_par_iter());
+            chunks.find_map_first(|chunk| chunk.into_iter().find_map(f))
+        }
+    }};
+    // `position_first` needs a *global* index, so each chunk's local match position is offset by
+    // `chunk_index * chunk_size` (the same chunk size `iter_subdivide` used to build `chunks`).
+    ( $concurrent_limit:expr, $iterator:expr, position_first, $predicate:expr ) => {{
+        let concurrent_limit = $concurrent_limit;
+        let predicate = $predicate;
+        if concurrent_limit == 0 {
+            $iterator.into_par_iter().position_first(predicate)
+        } else if concurrent_limit == 1 {
+            $iterator.into_iter().position(predicate)
+        } else {
+            let iterator = $iterator.into_par_iter();
+            let chunk_size = $crate::chunk_size_for(
+                rayon::iter::IndexedParallelIterator::len(&iterator),
+                concurrent_limit,
+            );
+            let chunks = $crate::iter_subdivide(concurrent_limit, iterator);
+            rayon::iter::IndexedParallelIterator::enumerate(chunks).find_map_first(|(chunk_index, chunk)| {
+                chunk
+                    .into_iter()
+                    .position(predicate)
+                    .map(|local_index| chunk_index * chunk_size + local_index)
+            })
+        }
+    }};
     // TODO: find_last?
-    // TODO: find_map_any?
-    // TODO: find_map_first?
     // TODO: find_map_last?
+    // TODO: position_any?
+    // TODO: position_last?
     ( $concurrent_limit:expr, $iterator:expr, any, $predicate:expr ) => {{
         let concurrent_limit = $concurrent_limit;
         let predicate = $predicate;
@@ -360,8 +710,224 @@ macro_rules! iter_concurrent_limit {
     // TODO: partition_map?
     // TODO: take_any_while?
     // TODO: skip_any_while?
-    // TODO: IndexedParallelIterator zip, zip_eq, fold_chunks, fold_chunks_with, cmp, partial_cmp, position_any, position_first, position_last, positions?
+    // TODO: IndexedParallelIterator zip, zip_eq, fold_chunks, fold_chunks_with, cmp, partial_cmp, position_any, position_last, positions?
     ( $concurrent_limit:expr, $iterator:expr, $method:ident, $predicate:expr ) => {{
         std::compile_error!("This macro does not support the requested method");
     }};
 }
+
+/// A counting semaphore built from [`std::sync::Mutex`] + [`std::sync::Condvar`], used by
+/// [`iter_concurrent_limit_balanced`] to cap the number of concurrently-running `op` invocations
+/// without reducing the number of work items rayon has to balance across its thread pool.
+pub struct Semaphore {
+    permits: std::sync::Mutex<usize>,
+    condvar: std::sync::Condvar,
+}
+
+impl Semaphore {
+    /// Create a new [`Semaphore`] with `permits` available permits.
+    #[must_use]
+    pub fn new(permits: usize) -> Self {
+        Self {
+            permits: std::sync::Mutex::new(permits),
+            condvar: std::sync::Condvar::new(),
+        }
+    }
+
+    /// Block until a permit is available, then acquire it. The permit is released when the
+    /// returned [`SemaphorePermit`] is dropped.
+    #[must_use]
+    pub fn acquire(&self) -> SemaphorePermit<'_> {
+        let mut permits = self.permits.lock().unwrap();
+        while *permits == 0 {
+            permits = self.condvar.wait(permits).unwrap();
+        }
+        *permits -= 1;
+        SemaphorePermit { semaphore: self }
+    }
+}
+
+/// A held permit from a [`Semaphore`], released back on drop.
+#[must_use]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        let mut permits = self.semaphore.permits.lock().unwrap();
+        *permits += 1;
+        drop(permits);
+        self.semaphore.condvar.notify_one();
+    }
+}
+
+/// The number of chunks subdivided per `concurrent_limit` permit in
+/// [`iter_concurrent_limit_balanced`], so rayon has enough small work items left to rebalance
+/// across its thread pool instead of running at the speed of the single slowest chunk.
+///
+/// `pub` (rather than crate-private) because [`iter_concurrent_limit_balanced`] expands to a
+/// reference to `$crate::BALANCED_OVERSUBSCRIPTION_FACTOR` in the caller's crate.
+pub const BALANCED_OVERSUBSCRIPTION_FACTOR: usize = 4;
+
+/// An alternative to [`iter_concurrent_limit`] that preserves rayon's work-stealing load balancing.
+///
+/// [`iter_concurrent_limit`] subdivides the iterator into exactly `concurrent_limit` chunks, which
+/// destroys rayon's ability to rebalance: if one chunk's items are much slower than another's, a
+/// worker stalls on the slow chunk while others sit idle once their own chunk finishes, and the
+/// whole operation runs at the speed of the slowest chunk. `iter_concurrent_limit_balanced`
+/// instead subdivides into many small chunks (`concurrent_limit * 4`, so rayon can still steal and
+/// rebalance work), and guards the user-supplied `op` with a counting [`Semaphore`] so that no more
+/// than `concurrent_limit` chunks run `op` at once.
+///
+/// # Critical invariant
+/// The active rayon thread pool must have at least `concurrent_limit` threads. If it has fewer,
+/// every thread can end up blocked waiting to acquire a permit that only a (nonexistent) other
+/// thread could release, deadlocking the pool.
+///
+/// Unlike [`rayon::ThreadPool::install`], only the outer `op` is throttled: nested parallel calls
+/// made from within `op` still use the full global thread pool, not a thread-limited one.
+///
+/// # Examples
+/// ### for_each
+/// ```rust
+/// # use rayon::iter::{IntoParallelIterator, ParallelIterator};
+/// # use rayon_iter_concurrent_limit::iter_concurrent_limit_balanced;
+/// let op = |_: usize| {
+///     // ... operation involving a large allocation, with variable per-item cost
+/// };
+/// iter_concurrent_limit_balanced!(2, (0..100), for_each, op);
+/// ```
+///
+/// ### map
+/// ```rust
+/// # use rayon::iter::{IntoParallelIterator, ParallelIterator};
+/// # use rayon_iter_concurrent_limit::iter_concurrent_limit_balanced;
+/// let op = |i: usize| -> usize { i * 2 };
+/// let output = iter_concurrent_limit_balanced!(2, (0..100), map, op).collect::<Vec<usize>>();
+/// assert_eq!(output, (0..100).into_iter().map(op).collect::<Vec<usize>>());
+/// ```
+#[macro_export]
+macro_rules! iter_concurrent_limit_balanced {
+    ( $concurrent_limit:expr, $iterator:expr, for_each, $op:expr ) => {{
+        let concurrent_limit = $concurrent_limit;
+        let op = $op;
+        if concurrent_limit == 0 {
+            $iterator.into_par_iter().for_each(op)
+        } else {
+            let semaphore = std::sync::Arc::new($crate::Semaphore::new(concurrent_limit));
+            let chunks = $crate::iter_subdivide(
+                concurrent_limit * $crate::BALANCED_OVERSUBSCRIPTION_FACTOR,
+                $iterator.into_par_iter(),
+            );
+            chunks.for_each(move |chunk| {
+                let _permit = semaphore.acquire();
+                chunk.into_iter().for_each(&op)
+            })
+        }
+    }};
+    ( $concurrent_limit:expr, $iterator:expr, map, $map_op:expr ) => {{
+        let concurrent_limit = $concurrent_limit;
+        let map_op = $map_op;
+        if concurrent_limit == 0 {
+            $crate::Either::Left($iterator.into_par_iter().map(map_op))
+        } else {
+            let semaphore = std::sync::Arc::new($crate::Semaphore::new(concurrent_limit));
+            let chunks = $crate::iter_subdivide(
+                concurrent_limit * $crate::BALANCED_OVERSUBSCRIPTION_FACTOR,
+                $iterator.into_par_iter(),
+            );
+            $crate::Either::Right(chunks.flat_map_iter(move |chunk| {
+                let _permit = semaphore.acquire();
+                // Eagerly collected so the permit stays held for the duration of the work, rather
+                // than being released as soon as this lazy iterator is constructed.
+                chunk.into_iter().map(&map_op).collect::<Vec<_>>().into_iter()
+            }))
+        }
+    }};
+}
+
+/// An ordered, bounded-prefetch adapter for pipelines that submit work (e.g. decoding a chunk) to
+/// the rayon pool but must consume results sequentially and in input order (e.g. writing decoded
+/// buffers out in order).
+///
+/// At most `limit` items are ever mid-flight at once: [`Iterator::next`] tops the queue of
+/// outstanding work back up to `limit` items before popping and blocking on the oldest one, so a
+/// slow consumer bounds memory use the same way a slow consumer of [`iter_concurrent_limit`]'s
+/// chunked iterators does, without sacrificing ordering.
+///
+/// # Examples
+/// ```rust
+/// use rayon_iter_concurrent_limit::iter_prefetch_ordered;
+/// let op = |i: usize| -> usize {
+///     // ... e.g. read and decode a chunk, with at most 2 outstanding at once
+///     i * 2
+/// };
+/// let output = iter_prefetch_ordered(2, 0..100, op).collect::<Vec<usize>>();
+/// assert_eq!(output, (0..100).map(op).collect::<Vec<usize>>());
+/// ```
+pub fn iter_prefetch_ordered<I, T, F, R>(
+    limit: usize,
+    iter: I,
+    op: F,
+) -> PrefetchOrdered<I::IntoIter, F, R>
+where
+    I: IntoIterator<Item = T>,
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> R + Send + Sync + 'static,
+{
+    PrefetchOrdered {
+        input: iter.into_iter(),
+        op: std::sync::Arc::new(op),
+        queue: std::collections::VecDeque::new(),
+        limit: std::cmp::max(limit, 1),
+    }
+}
+
+/// The bounded-prefetch [`Iterator`] returned by [`iter_prefetch_ordered`].
+pub struct PrefetchOrdered<I, F, R> {
+    input: I,
+    op: std::sync::Arc<F>,
+    queue: std::collections::VecDeque<std::sync::mpsc::Receiver<R>>,
+    limit: usize,
+}
+
+impl<I, T, F, R> Iterator for PrefetchOrdered<I, F, R>
+where
+    I: Iterator<Item = T>,
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> R + Send + Sync + 'static,
+{
+    type Item = R;
+
+    fn next(&mut self) -> Option<R> {
+        while self.queue.len() < self.limit {
+            match self.input.next() {
+                Some(item) => self.queue.push_back(Self::spawn(&self.op, item)),
+                None => break,
+            }
+        }
+        self.queue
+            .pop_front()
+            .map(|receiver| receiver.recv().expect("iter_prefetch_ordered: op panicked"))
+    }
+}
+
+impl<I, T, F, R> PrefetchOrdered<I, F, R>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> R + Send + Sync + 'static,
+{
+    /// Submit `op(item)` to the rayon pool, returning a receiver for its result.
+    fn spawn(op: &std::sync::Arc<F>, item: T) -> std::sync::mpsc::Receiver<R> {
+        let op = std::sync::Arc::clone(op);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        rayon::spawn(move || {
+            let _ = sender.send(op(item));
+        });
+        receiver
+    }
+}
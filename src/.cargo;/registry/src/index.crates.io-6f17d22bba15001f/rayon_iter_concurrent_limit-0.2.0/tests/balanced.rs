@@ -0,0 +1,65 @@
+mod common;
+use core::time;
+use std::sync::atomic::AtomicUsize;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use rayon_iter_concurrent_limit::iter_concurrent_limit_balanced;
+
+use common::{calc_active_operations, incr_active_operations};
+
+const DUR: time::Duration = core::time::Duration::from_millis(10);
+
+#[test]
+fn iter_concurrent_limit_balanced_for_each_respects_limit() {
+    let threads_active = AtomicUsize::new(0);
+    let threads_active_max = AtomicUsize::new(0);
+    iter_concurrent_limit_balanced!(2, (0..100), for_each, |_: usize| {
+        incr_active_operations(&threads_active);
+        std::thread::sleep(DUR);
+        calc_active_operations(&threads_active, &threads_active_max);
+    });
+    assert!(threads_active_max.into_inner() <= 2);
+}
+
+#[test]
+fn iter_concurrent_limit_balanced_for_each_0_bypasses_semaphore() {
+    let seen = std::sync::Mutex::new(Vec::new());
+    iter_concurrent_limit_balanced!(0, (0..10), for_each, |i: usize| {
+        seen.lock().unwrap().push(i);
+    });
+    let mut seen = seen.into_inner().unwrap();
+    seen.sort_unstable();
+    assert_eq!(seen, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn iter_concurrent_limit_balanced_map_respects_limit() {
+    let threads_active = AtomicUsize::new(0);
+    let threads_active_max = AtomicUsize::new(0);
+    let output = iter_concurrent_limit_balanced!(2, (0..100), map, |i: usize| -> usize {
+        incr_active_operations(&threads_active);
+        std::thread::sleep(DUR);
+        calc_active_operations(&threads_active, &threads_active_max);
+        i * 2
+    })
+    .collect::<Vec<_>>();
+    let mut sorted = output.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..100).map(|i| i * 2).collect::<Vec<_>>());
+    assert!(threads_active_max.into_inner() <= 2);
+}
+
+#[test]
+fn iter_concurrent_limit_balanced_map_preserves_order() {
+    let output = iter_concurrent_limit_balanced!(3, (0..50), map, |i: usize| i)
+        .collect::<Vec<_>>();
+    assert_eq!(output, (0..50).collect::<Vec<_>>());
+}
+
+#[test]
+fn iter_concurrent_limit_balanced_map_0_bypasses_semaphore() {
+    let output = iter_concurrent_limit_balanced!(0, (0..10), map, |i: usize| i + 1)
+        .collect::<Vec<_>>();
+    assert_eq!(output, (1..=10).collect::<Vec<_>>());
+}
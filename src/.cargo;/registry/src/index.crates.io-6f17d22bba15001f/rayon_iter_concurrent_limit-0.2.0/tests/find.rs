@@ -0,0 +1,75 @@
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+#[test]
+fn iter_concurrent_limit_find_any_finds_the_value() {
+    let found = iter_concurrent_limit!(2, (0..100), find_any, |i: &usize| *i == 50);
+    assert_eq!(found, Some(50));
+}
+
+#[test]
+fn iter_concurrent_limit_find_any_no_match() {
+    let found = iter_concurrent_limit!(2, (0..10), find_any, |i: &usize| *i == 50);
+    assert_eq!(found, None);
+}
+
+#[test]
+fn iter_concurrent_limit_find_map_any_finds_the_value() {
+    let found = iter_concurrent_limit!(
+        2,
+        (0..100),
+        find_map_any,
+        |i: usize| if i == 50 { Some(i * 2) } else { None }
+    );
+    assert_eq!(found, Some(100));
+}
+
+#[test]
+fn iter_concurrent_limit_find_first_finds_the_earliest_match() {
+    let found = iter_concurrent_limit!(4, (0..100), find_first, |i: &usize| *i % 10 == 0 && *i > 0);
+    assert_eq!(found, Some(10));
+}
+
+#[test]
+fn iter_concurrent_limit_find_first_no_match() {
+    let found = iter_concurrent_limit!(4, (0..10), find_first, |i: &usize| *i == 50);
+    assert_eq!(found, None);
+}
+
+#[test]
+fn iter_concurrent_limit_find_map_first_finds_the_earliest_match() {
+    let found = iter_concurrent_limit!(
+        4,
+        (0..100),
+        find_map_first,
+        |i: usize| if i % 10 == 0 && i > 0 { Some(i) } else { None }
+    );
+    assert_eq!(found, Some(10));
+}
+
+#[test]
+fn iter_concurrent_limit_position_first_returns_a_global_index() {
+    // `position_first` must offset each chunk's local match by that chunk's starting index, not
+    // just report the position within whichever chunk happened to match.
+    let position = iter_concurrent_limit!(4, (0..100), position_first, |i: usize| i == 73);
+    assert_eq!(position, Some(73));
+}
+
+#[test]
+fn iter_concurrent_limit_position_first_no_match() {
+    let position = iter_concurrent_limit!(4, (0..10), position_first, |i: usize| i == 50);
+    assert_eq!(position, None);
+}
+
+#[test]
+fn iter_concurrent_limit_find_first_1_matches_sequential_find() {
+    let found = iter_concurrent_limit!(1, (0..100), find_first, |i: &usize| *i == 42);
+    assert_eq!(found, (0..100).into_iter().find(|i| *i == 42));
+}
+
+#[test]
+fn iter_concurrent_limit_find_first_0_bypasses_chunking() {
+    let found = iter_concurrent_limit!(0, (0..100), find_first, |i: &usize| *i == 42);
+    assert_eq!(found, Some(42));
+}
@@ -0,0 +1,84 @@
+mod common;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+#[test]
+fn iter_concurrent_limit_map_init_one_state_per_chunk() {
+    let inits = AtomicUsize::new(0);
+    let op = |buf: &mut Vec<usize>, i: usize| -> usize {
+        buf.push(i);
+        buf.len()
+    };
+    let counts = iter_concurrent_limit!(
+        2,
+        (0..10),
+        map_init,
+        || {
+            inits.fetch_add(1, Ordering::SeqCst);
+            Vec::new()
+        },
+        op
+    )
+    .collect::<Vec<_>>();
+    assert_eq!(counts.len(), 10);
+    assert_eq!(inits.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn iter_concurrent_limit_map_with_clones_seed_per_chunk() {
+    let op = |buf: &mut Vec<usize>, i: usize| -> usize {
+        buf.push(i);
+        buf.len()
+    };
+    let counts = iter_concurrent_limit!(2, (0..10), map_with, Vec::<usize>::new(), op)
+        .collect::<Vec<_>>();
+    assert_eq!(counts.len(), 10);
+}
+
+#[test]
+fn iter_concurrent_limit_for_each_init_one_state_per_chunk() {
+    let inits = AtomicUsize::new(0);
+    let seen = Mutex::new(Vec::new());
+    let op = |buf: &mut Vec<usize>, i: usize| {
+        buf.push(i);
+    };
+    iter_concurrent_limit!(
+        2,
+        (0..10),
+        for_each_init,
+        || {
+            inits.fetch_add(1, Ordering::SeqCst);
+            Vec::new()
+        },
+        |buf: &mut Vec<usize>, i: usize| {
+            op(buf, i);
+            seen.lock().unwrap().push(i);
+        }
+    );
+    assert_eq!(inits.load(Ordering::SeqCst), 2);
+    let mut seen = seen.into_inner().unwrap();
+    seen.sort_unstable();
+    assert_eq!(seen, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn iter_concurrent_limit_for_each_with_clones_seed_per_chunk() {
+    let seen = Mutex::new(Vec::new());
+    iter_concurrent_limit!(
+        2,
+        (0..10),
+        for_each_with,
+        Vec::<usize>::new(),
+        |buf: &mut Vec<usize>, i: usize| {
+            buf.push(i);
+            seen.lock().unwrap().push(i);
+        }
+    );
+    let mut seen = seen.into_inner().unwrap();
+    seen.sort_unstable();
+    assert_eq!(seen, (0..10).collect::<Vec<_>>());
+}
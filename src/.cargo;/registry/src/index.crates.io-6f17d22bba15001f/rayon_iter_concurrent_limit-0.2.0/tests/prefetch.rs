@@ -0,0 +1,52 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use rayon_iter_concurrent_limit::iter_prefetch_ordered;
+
+#[test]
+fn iter_prefetch_ordered_preserves_order() {
+    let output = iter_prefetch_ordered(3, 0..100, |i: usize| i * 2).collect::<Vec<_>>();
+    assert_eq!(output, (0..100).map(|i| i * 2).collect::<Vec<_>>());
+}
+
+#[test]
+fn iter_prefetch_ordered_bounds_outstanding_work() {
+    let outstanding = AtomicUsize::new(0);
+    let outstanding_max = AtomicUsize::new(0);
+    let output = iter_prefetch_ordered(2, 0..20, |i: usize| {
+        outstanding.fetch_add(1, Ordering::SeqCst);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let n_active = outstanding.fetch_sub(1, Ordering::SeqCst);
+        outstanding_max.fetch_max(n_active, Ordering::SeqCst);
+        i
+    })
+    .collect::<Vec<_>>();
+    assert_eq!(output, (0..20).collect::<Vec<_>>());
+    assert!(outstanding_max.load(Ordering::SeqCst) <= 2);
+}
+
+#[test]
+fn iter_prefetch_ordered_empty_input() {
+    let output = iter_prefetch_ordered(4, 0..0, |i: usize| i).collect::<Vec<_>>();
+    assert!(output.is_empty());
+}
+
+#[test]
+fn iter_prefetch_ordered_limit_0_treated_as_1() {
+    let output = iter_prefetch_ordered(0, 0..10, |i: usize| i + 1).collect::<Vec<_>>();
+    assert_eq!(output, (1..=10).collect::<Vec<_>>());
+}
+
+#[test]
+fn iter_prefetch_ordered_runs_op_exactly_once_per_item() {
+    let seen = Mutex::new(Vec::new());
+    let output = iter_prefetch_ordered(3, 0..10, |i: usize| {
+        seen.lock().unwrap().push(i);
+        i
+    })
+    .collect::<Vec<_>>();
+    assert_eq!(output, (0..10).collect::<Vec<_>>());
+    let mut seen = seen.into_inner().unwrap();
+    seen.sort_unstable();
+    assert_eq!(seen, (0..10).collect::<Vec<_>>());
+}
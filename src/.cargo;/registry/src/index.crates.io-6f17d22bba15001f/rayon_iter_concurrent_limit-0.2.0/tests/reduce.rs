@@ -0,0 +1,80 @@
+mod common;
+use core::time;
+use std::sync::atomic::AtomicUsize;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+use common::{calc_active_operations, incr_active_operations};
+
+const DUR: time::Duration = core::time::Duration::from_millis(10);
+
+fn iter_concurrent_limit_reduce(concurrent_limit: usize) {
+    let threads_active = AtomicUsize::new(0);
+    let threads_active_max = AtomicUsize::new(0);
+    let sum = iter_concurrent_limit!(concurrent_limit, (0..10), reduce, || 0usize, |a: usize, b: usize| {
+        incr_active_operations(&threads_active);
+        std::thread::sleep(DUR);
+        calc_active_operations(&threads_active, &threads_active_max);
+        a + b
+    });
+    assert_eq!(sum, (0..10).sum::<usize>());
+}
+
+#[test]
+fn iter_concurrent_limit_reduce_1() {
+    iter_concurrent_limit_reduce(1);
+}
+
+#[test]
+fn iter_concurrent_limit_reduce_2() {
+    iter_concurrent_limit_reduce(2);
+}
+
+#[test]
+fn iter_concurrent_limit_reduce_0_bypasses_chunking() {
+    let sum = iter_concurrent_limit!(0, (0..10), reduce, || 0usize, |a: usize, b: usize| a + b);
+    assert_eq!(sum, (0..10).sum::<usize>());
+}
+
+#[test]
+fn iter_concurrent_limit_reduce_empty() {
+    let sum = iter_concurrent_limit!(2, (0..0), reduce, || 0usize, |a: usize, b: usize| a + b);
+    assert_eq!(sum, 0);
+}
+
+#[test]
+fn iter_concurrent_limit_fold() {
+    // `fold` only folds within each chunk; the caller combines the per-chunk accumulators.
+    let sum = iter_concurrent_limit!(2, (0..10), fold, || 0usize, |a: usize, b: usize| a + b)
+        .sum::<usize>();
+    assert_eq!(sum, (0..10).sum::<usize>());
+}
+
+#[test]
+fn iter_concurrent_limit_fold_empty() {
+    let chunks = iter_concurrent_limit!(2, (0..0), fold, || 0usize, |a: usize, b: usize| a + b)
+        .collect::<Vec<_>>();
+    assert!(chunks.is_empty());
+}
+
+#[test]
+fn iter_concurrent_limit_try_fold_ok() {
+    let sum = iter_concurrent_limit!(2, (0..10), try_fold, || 0usize, |a: usize, b: usize| {
+        Ok::<usize, &'static str>(a + b)
+    });
+    assert_eq!(sum, Ok((0..10).sum::<usize>()));
+}
+
+#[test]
+fn iter_concurrent_limit_try_fold_short_circuits() {
+    let result = iter_concurrent_limit!(2, (0..10), try_fold, || 0usize, |a: usize, b: usize| {
+        if b == 5 {
+            Err("hit 5")
+        } else {
+            Ok(a + b)
+        }
+    });
+    assert_eq!(result, Err("hit 5"));
+}
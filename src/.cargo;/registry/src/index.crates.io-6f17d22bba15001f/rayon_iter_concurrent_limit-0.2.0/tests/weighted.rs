@@ -0,0 +1,55 @@
+mod common;
+use core::time;
+use std::sync::atomic::AtomicUsize;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+use common::{calc_active_operations, incr_active_operations};
+
+const DUR: time::Duration = core::time::Duration::from_millis(10);
+
+#[test]
+fn iter_concurrent_limit_map_weighted_uniform_weight() {
+    // With a uniform weight of 1 per item, a budget of `n` behaves like concurrent_limit = n.
+    let threads_active = AtomicUsize::new(0);
+    let threads_active_max = AtomicUsize::new(0);
+    let output = iter_concurrent_limit!(2, (0..10), map, weighted, |_: &usize| 1usize, |i: usize| -> usize {
+        incr_active_operations(&threads_active);
+        std::thread::sleep(DUR);
+        calc_active_operations(&threads_active, &threads_active_max);
+        i * 2
+    })
+    .collect::<Vec<_>>();
+    let mut sorted = output.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..10).into_iter().map(|i| i * 2).collect::<Vec<_>>());
+    assert!(threads_active_max.into_inner() <= 2);
+}
+
+#[test]
+fn iter_concurrent_limit_map_weighted_oversized_item_makes_progress() {
+    // A single item whose weight exceeds the budget must still run (once nothing else is in
+    // flight) rather than deadlocking.
+    let output = iter_concurrent_limit!(1, (0..3), map, weighted, |_: &usize| 100usize, |i: usize| -> usize {
+        i + 1
+    })
+    .collect::<Vec<_>>();
+    let mut sorted = output.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, vec![1, 2, 3]);
+}
+
+#[test]
+fn iter_concurrent_limit_for_each_weighted_respects_budget() {
+    let threads_active = AtomicUsize::new(0);
+    let threads_active_max = AtomicUsize::new(0);
+    iter_concurrent_limit!(4, (0..10), for_each, weighted, |i: &usize| *i % 3 + 1, |i: usize| {
+        incr_active_operations(&threads_active);
+        std::thread::sleep(DUR);
+        calc_active_operations(&threads_active, &threads_active_max);
+        let _ = i;
+    });
+    assert!(threads_active_max.into_inner() <= 4);
+}
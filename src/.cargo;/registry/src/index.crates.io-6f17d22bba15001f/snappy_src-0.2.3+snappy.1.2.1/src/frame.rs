@@ -0,0 +1,299 @@
+//! The [Snappy framing format](https://github.com/google/snappy/blob/main/framing_format.txt),
+//! layered on top of the raw-block [`snappy_compress`]/[`snappy_uncompress`] bindings.
+//!
+//! The raw bindings cap a single call at the ~4 GiB block limit and have no notion of a stream.
+//! The framing format splits arbitrary-length data into a sequence of chunks, each individually
+//! checksummed, so it can be streamed and interoperate with other tools' `.sz` files.
+
+use crate::{
+    snappy_compress, snappy_max_compressed_length, snappy_status_SNAPPY_OK, snappy_uncompress,
+    snappy_uncompressed_length,
+};
+
+/// The maximum number of uncompressed bytes held by a single data chunk.
+const MAX_UNCOMPRESSED_CHUNK_SIZE: usize = 65536;
+
+const IDENTIFIER_CHUNK_TAG: u8 = 0xff;
+const COMPRESSED_CHUNK_TAG: u8 = 0x00;
+const UNCOMPRESSED_CHUNK_TAG: u8 = 0x01;
+const STREAM_IDENTIFIER: &[u8; 6] = b"sNaPpY";
+
+/// An error encountered while decompressing a Snappy framing-format stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameDecompressError {
+    /// The stream did not begin with the identifier chunk.
+    MissingStreamIdentifier,
+    /// An identifier chunk was present but its body did not match `"sNaPpY"`.
+    InvalidStreamIdentifier,
+    /// The stream ended in the middle of a chunk header or body.
+    Truncated,
+    /// A chunk used a reserved, unskippable tag (`0x02..=0x7f`) that this decoder does not understand.
+    UnsupportedChunk(u8),
+    /// The masked CRC-32C of a data chunk did not match its decompressed contents.
+    ChecksumMismatch,
+    /// The underlying `snappy_uncompress` call failed.
+    SnappyError,
+}
+
+impl std::fmt::Display for FrameDecompressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingStreamIdentifier => write!(f, "missing Snappy stream identifier chunk"),
+            Self::InvalidStreamIdentifier => write!(f, "invalid Snappy stream identifier chunk"),
+            Self::Truncated => write!(f, "truncated Snappy framing-format stream"),
+            Self::UnsupportedChunk(tag) => {
+                write!(f, "unsupported unskippable Snappy chunk type {tag:#04x}")
+            }
+            Self::ChecksumMismatch => write!(f, "Snappy chunk checksum mismatch"),
+            Self::SnappyError => write!(f, "snappy_uncompress failed"),
+        }
+    }
+}
+
+impl std::error::Error for FrameDecompressError {}
+
+/// Compress `data` to the Snappy framing (stream) format.
+///
+/// The output begins with the stream identifier chunk, followed by one data chunk per (at most
+/// [`MAX_UNCOMPRESSED_CHUNK_SIZE`]-byte) block of `data`. A block is stored as a raw
+/// (uncompressed) chunk if compressing it does not shrink it.
+#[must_use]
+pub fn compress_frame(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + data.len() / 6 + 64);
+    write_chunk(&mut out, IDENTIFIER_CHUNK_TAG, STREAM_IDENTIFIER);
+    for block in data.chunks(MAX_UNCOMPRESSED_CHUNK_SIZE) {
+        let masked_crc = mask_crc32c(crc32c(block)).to_le_bytes();
+        let compressed = compress_block(block);
+        if compressed.len() < block.len() {
+            let mut body = Vec::with_capacity(4 + compressed.len());
+            body.extend_from_slice(&masked_crc);
+            body.extend_from_slice(&compressed);
+            write_chunk(&mut out, COMPRESSED_CHUNK_TAG, &body);
+        } else {
+            let mut body = Vec::with_capacity(4 + block.len());
+            body.extend_from_slice(&masked_crc);
+            body.extend_from_slice(block);
+            write_chunk(&mut out, UNCOMPRESSED_CHUNK_TAG, &body);
+        }
+    }
+    out
+}
+
+/// Decompress a Snappy framing (stream) format buffer produced by [`compress_frame`] (or another
+/// conforming encoder).
+///
+/// # Errors
+/// Returns [`FrameDecompressError`] if the stream is truncated, missing its identifier chunk,
+/// uses a chunk type this decoder does not understand, or fails a chunk checksum.
+pub fn decompress_frame(data: &[u8]) -> Result<Vec<u8>, FrameDecompressError> {
+    let mut pos = 0;
+    let mut out = Vec::new();
+    let mut seen_identifier = false;
+    while pos < data.len() {
+        let (tag, body, next_pos) = read_chunk(data, pos)?;
+        pos = next_pos;
+        match tag {
+            IDENTIFIER_CHUNK_TAG => {
+                if body != STREAM_IDENTIFIER {
+                    return Err(FrameDecompressError::InvalidStreamIdentifier);
+                }
+                seen_identifier = true;
+            }
+            COMPRESSED_CHUNK_TAG | UNCOMPRESSED_CHUNK_TAG => {
+                if !seen_identifier {
+                    return Err(FrameDecompressError::MissingStreamIdentifier);
+                }
+                if body.len() < 4 {
+                    return Err(FrameDecompressError::Truncated);
+                }
+                let masked_crc = u32::from_le_bytes(body[..4].try_into().unwrap());
+                let payload = &body[4..];
+                let chunk = if tag == COMPRESSED_CHUNK_TAG {
+                    decompress_block(payload)?
+                } else {
+                    payload.to_vec()
+                };
+                if unmask_crc32c(masked_crc) != crc32c(&chunk) {
+                    return Err(FrameDecompressError::ChecksumMismatch);
+                }
+                out.extend_from_slice(&chunk);
+            }
+            // 0x02..=0x7f: reserved unskippable chunks we don't understand.
+            0x02..=0x7f => return Err(FrameDecompressError::UnsupportedChunk(tag)),
+            // 0x80..=0xfe: reserved skippable chunks (including padding) are simply ignored.
+            _ => {}
+        }
+    }
+    if !seen_identifier {
+        return Err(FrameDecompressError::MissingStreamIdentifier);
+    }
+    Ok(out)
+}
+
+/// Read one `tag, 3-byte-LE-length, body` chunk from `data` starting at `pos`, returning the tag,
+/// the body slice, and the position immediately after the chunk.
+fn read_chunk(data: &[u8], pos: usize) -> Result<(u8, &[u8], usize), FrameDecompressError> {
+    if data.len() - pos < 4 {
+        return Err(FrameDecompressError::Truncated);
+    }
+    let tag = data[pos];
+    let len = u32::from_le_bytes([data[pos + 1], data[pos + 2], data[pos + 3], 0]) as usize;
+    let body_start = pos + 4;
+    if data.len() - body_start < len {
+        return Err(FrameDecompressError::Truncated);
+    }
+    Ok((tag, &data[body_start..body_start + len], body_start + len))
+}
+
+/// Append one `tag, 3-byte-LE-length, body` chunk to `out`.
+fn write_chunk(out: &mut Vec<u8>, tag: u8, body: &[u8]) {
+    out.push(tag);
+    out.extend_from_slice(&(body.len() as u32).to_le_bytes()[..3]);
+    out.extend_from_slice(body);
+}
+
+/// Compress `block` with raw (non-framed) Snappy.
+fn compress_block(block: &[u8]) -> Vec<u8> {
+    let mut len = unsafe { snappy_max_compressed_length(block.len()) };
+    let mut dst = Vec::<u8>::with_capacity(len);
+    unsafe {
+        assert_eq!(
+            snappy_status_SNAPPY_OK,
+            snappy_compress(
+                block.as_ptr().cast(),
+                block.len(),
+                dst.as_mut_ptr().cast(),
+                &mut len,
+            )
+        );
+        dst.set_len(len);
+    }
+    dst
+}
+
+/// Decompress a raw (non-framed) Snappy `block`.
+fn decompress_block(block: &[u8]) -> Result<Vec<u8>, FrameDecompressError> {
+    let mut len = 0;
+    if unsafe { snappy_uncompressed_length(block.as_ptr().cast(), block.len(), &mut len) }
+        != snappy_status_SNAPPY_OK
+    {
+        return Err(FrameDecompressError::SnappyError);
+    }
+    let mut dst = Vec::<u8>::with_capacity(len);
+    unsafe {
+        if snappy_uncompress(block.as_ptr().cast(), block.len(), dst.as_mut_ptr().cast(), &mut len)
+            != snappy_status_SNAPPY_OK
+        {
+            return Err(FrameDecompressError::SnappyError);
+        }
+        dst.set_len(len);
+    }
+    Ok(dst)
+}
+
+/// Mask a CRC-32C as specified by the Snappy framing format.
+fn mask_crc32c(crc: u32) -> u32 {
+    ((crc >> 15) | (crc << 17)).wrapping_add(0xa282_ead8)
+}
+
+/// Reverse [`mask_crc32c`].
+fn unmask_crc32c(masked_crc: u32) -> u32 {
+    let rot = masked_crc.wrapping_sub(0xa282_ead8);
+    (rot >> 17) | (rot << 15)
+}
+
+/// CRC-32C (Castagnoli) of `data`, computed bit-by-bit since this crate has no existing CRC
+/// dependency to reuse.
+fn crc32c(data: &[u8]) -> u32 {
+    const REVERSED_POLY: u32 = 0x82f6_3b78;
+    let mut crc = 0xffff_ffff_u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = 0u32.wrapping_sub(crc & 1);
+            crc = (crc >> 1) ^ (REVERSED_POLY & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32c_known_vector() {
+        // CRC-32C("123456789") = 0xe3069283, the standard check value for the Castagnoli polynomial.
+        assert_eq!(crc32c(b"123456789"), 0xe306_9283);
+    }
+
+    #[test]
+    fn mask_unmask_roundtrip() {
+        let crc = crc32c(b"hello world");
+        assert_eq!(unmask_crc32c(mask_crc32c(crc)), crc);
+    }
+
+    #[test]
+    fn frame_roundtrip_empty() {
+        let compressed = compress_frame(&[]);
+        assert_eq!(decompress_frame(&compressed).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn frame_roundtrip_small() {
+        let data = b"hello hello hello hello world".to_vec();
+        let compressed = compress_frame(&data);
+        assert_eq!(decompress_frame(&compressed).unwrap(), data);
+    }
+
+    #[test]
+    fn frame_roundtrip_multi_chunk() {
+        let data = (0..(3 * MAX_UNCOMPRESSED_CHUNK_SIZE + 123))
+            .map(|i| (i % 256) as u8)
+            .collect::<Vec<u8>>();
+        let compressed = compress_frame(&data);
+        assert_eq!(decompress_frame(&compressed).unwrap(), data);
+    }
+
+    #[test]
+    fn missing_identifier_errors() {
+        let mut out = Vec::new();
+        write_chunk(&mut out, UNCOMPRESSED_CHUNK_TAG, &[0, 0, 0, 0]);
+        assert_eq!(
+            decompress_frame(&out),
+            Err(FrameDecompressError::MissingStreamIdentifier)
+        );
+    }
+
+    #[test]
+    fn checksum_mismatch_errors() {
+        let mut compressed = compress_frame(b"hello world");
+        // Flip a byte in the CRC field of the data chunk (right after the 4-byte chunk header and
+        // the stream identifier chunk).
+        let data_chunk_start = 4 + STREAM_IDENTIFIER.len() + 4;
+        compressed[data_chunk_start] ^= 0xff;
+        assert_eq!(
+            decompress_frame(&compressed),
+            Err(FrameDecompressError::ChecksumMismatch)
+        );
+    }
+
+    #[test]
+    fn unsupported_chunk_errors() {
+        let mut out = Vec::new();
+        write_chunk(&mut out, IDENTIFIER_CHUNK_TAG, STREAM_IDENTIFIER);
+        write_chunk(&mut out, 0x02, &[]);
+        assert_eq!(
+            decompress_frame(&out),
+            Err(FrameDecompressError::UnsupportedChunk(0x02))
+        );
+    }
+
+    #[test]
+    fn skippable_chunk_is_ignored() {
+        let mut out = Vec::new();
+        write_chunk(&mut out, IDENTIFIER_CHUNK_TAG, STREAM_IDENTIFIER);
+        write_chunk(&mut out, 0xfe, &[1, 2, 3]); // padding
+        assert_eq!(decompress_frame(&out).unwrap(), Vec::<u8>::new());
+    }
+}
@@ -8,6 +8,13 @@
 //! cargo build --features bindgen
 //! ```
 //!
+//! ## Framing format
+//! The raw `snappy_compress`/`snappy_uncompress` bindings only handle a single block, capped at the
+//! ~4 GiB block limit. [`compress_frame`] and [`decompress_frame`] implement the
+//! [Snappy framing (stream) format](https://github.com/google/snappy/blob/main/framing_format.txt)
+//! on top of those bindings, for streaming arbitrary-length data and interoperating with other
+//! tools' `.sz` streams.
+//!
 //! ## Licence
 //! `snappy_src` is licensed under either of
 //!  - the Apache License, Version 2.0 [LICENSE-APACHE](./LICENCE-APACHE) or <http://www.apache.org/licenses/LICENSE-2.0> or
@@ -22,3 +29,6 @@
 extern crate link_cplusplus;
 
 include!(concat!(env!("CARGO_MANIFEST_DIR"), "/bindings.rs"));
+
+mod frame;
+pub use frame::{compress_frame, decompress_frame, FrameDecompressError};
@@ -40,6 +40,25 @@
 //!
 //! Under the hood, [`UnsafeCellSlice`] is a reference to a [`std::cell::UnsafeCell`] slice, hence the name of the crate.
 //!
+//! ### Writing into uninitialised memory
+//! [`UnsafeCellSlice<MaybeUninit<T>>`](std::mem::MaybeUninit) provides a sound way to fill the spare
+//! capacity of a [`Vec`] from parallel writers. Reinterpreting uninitialised bytes as `&mut [T]` (as
+//! [`new_from_vec_with_spare_capacity`](UnsafeCellSlice::new_from_vec_with_spare_capacity) does) is
+//! undefined behaviour for any `T` with validity invariants, even before a value is written. The
+//! `MaybeUninit`-typed constructor [`new_uninit_from_vec_with_spare_capacity`](UnsafeCellSlice::new_uninit_from_vec_with_spare_capacity)
+//! avoids this by exposing the spare capacity as `&mut [MaybeUninit<T>]` (via
+//! [`as_mut_uninit_slice`](UnsafeCellSlice::as_mut_uninit_slice))
+//! and only requiring `unsafe` for the element-wise [`write`](UnsafeCellSlice::write) and the final
+//! [`assume_init_len`](UnsafeCellSlice::assume_init_len).
+//!
+//! ### Disjoint partitioning
+//! [`split_at_mut`](slice::split_at_mut)/[`chunks_mut`](slice::chunks_mut) are the safe route for
+//! disjoint-by-region parallel writes, but they can be too rigid for some use cases.
+//! [`indices`](UnsafeCellSlice::indices) narrows an [`UnsafeCellSlice`] to a sub-range, and in debug
+//! builds [`as_mut_slice_for`](UnsafeCellSlice::as_mut_slice_for) tracks every range handed out and
+//! panics on overlap, giving a middle ground between the fully-safe and fully-unsafe APIs. The
+//! tracker is compiled away entirely in release builds.
+//!
 //! ## Licence
 //! `unsafe_cell_slice` is licensed under either of
 //!  - the Apache License, Version 2.0 [LICENSE-APACHE](https://docs.rs/crate/unsafe_cell_slice/latest/source/LICENCE-APACHE) or <http://www.apache.org/licenses/LICENSE-2.0> or
@@ -50,19 +69,53 @@
 /// An unsafe cell slice. Permits acquisition of multiple mutable references to a slice.
 ///
 /// This is inherently unsafe and it is the responsibility of the caller to avoid data races and undefined behavior.
-#[derive(Copy, Clone)]
-pub struct UnsafeCellSlice<'a, T>(&'a [std::cell::UnsafeCell<T>]);
+#[derive(Clone)]
+pub struct UnsafeCellSlice<'a, T> {
+    slice: &'a [std::cell::UnsafeCell<T>],
+    /// Ranges handed out via [`as_mut_slice_for`](UnsafeCellSlice::as_mut_slice_for), tracked only
+    /// in debug builds so that overlapping writers are caught in tests at no cost in release. An
+    /// `Arc` (rather than a leaked `Box`) so the tracker is freed once every clone of this
+    /// `UnsafeCellSlice` has been dropped.
+    #[cfg(debug_assertions)]
+    overlap_tracker: std::sync::Arc<std::sync::Mutex<Vec<std::ops::Range<usize>>>>,
+}
+
+// In release builds `UnsafeCellSlice` is just a slice reference, so it stays `Copy`; in debug
+// builds it also carries a refcounted overlap tracker, so only `Clone` (the `Arc` bump) applies.
+#[cfg(not(debug_assertions))]
+impl<'a, T: Copy> Copy for UnsafeCellSlice<'a, T> {}
 
 unsafe impl<'a, T: Send + Sync> Send for UnsafeCellSlice<'a, T> {}
 unsafe impl<'a, T: Send + Sync> Sync for UnsafeCellSlice<'a, T> {}
 
+impl<'a, T> UnsafeCellSlice<'a, T> {
+    fn from_raw(slice: &'a [std::cell::UnsafeCell<T>]) -> Self {
+        Self {
+            slice,
+            #[cfg(debug_assertions)]
+            overlap_tracker: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Narrow this [`UnsafeCellSlice`] to the sub-range `range`, with its own independent overlap
+    /// tracker in debug builds.
+    ///
+    /// This is a runtime-checked middle ground between [`slice::split_at_mut`]/[`slice::chunks_mut`]
+    /// (safe but rigid) and [`as_mut_slice`](UnsafeCellSlice::as_mut_slice) (no guardrails at all):
+    /// each task can narrow to the region it owns before dropping to `unsafe`.
+    #[must_use]
+    pub fn indices(&self, range: std::ops::Range<usize>) -> Self {
+        Self::from_raw(&self.slice[range])
+    }
+}
+
 impl<'a, T: Copy> UnsafeCellSlice<'a, T> {
     /// Create a new [`UnsafeCellSlice`] from a mutable slice.
     #[must_use]
     pub fn new(slice: &'a mut [T]) -> Self {
         // Rust 1.76: std::ptr::from_mut::<[T]>(slice)
         let ptr = slice as *mut [T] as *const [std::cell::UnsafeCell<T>];
-        Self(unsafe { &*ptr })
+        Self::from_raw(unsafe { &*ptr })
     }
 
     /// Create a new [`UnsafeCellSlice`] from the spare capacity in a [`Vec`].
@@ -80,8 +133,83 @@ impl<'a, T: Copy> UnsafeCellSlice<'a, T> {
     #[must_use]
     #[allow(clippy::mut_from_ref)]
     pub unsafe fn as_mut_slice(&self) -> &mut [T] {
-        let ptr = self.0[0].get();
-        std::slice::from_raw_parts_mut(ptr, self.0.len())
+        let ptr = self.slice[0].get();
+        std::slice::from_raw_parts_mut(ptr, self.slice.len())
+    }
+
+    /// Get a mutable reference to the sub-slice covering `range`.
+    ///
+    /// In debug builds, `range` is recorded in an overlap tracker shared between all copies of this
+    /// [`UnsafeCellSlice`]: if it overlaps a range still outstanding (i.e. whose guard has not yet
+    /// been dropped) this panics instead of silently aliasing; the range is removed again once the
+    /// returned [`UnsafeCellSliceGuard`] is dropped, so the same region can be borrowed again
+    /// afterwards. In release builds the tracker compiles away entirely, so this is equivalent to
+    /// slicing [`as_mut_slice`](UnsafeCellSlice::as_mut_slice).
+    ///
+    /// # Safety
+    /// Besides the same safety requirements as [`as_mut_slice`](UnsafeCellSlice::as_mut_slice), `range`
+    /// must be in bounds. The debug-mode overlap check is a best-effort aid for catching aliasing bugs
+    /// in tests, not a soundness guarantee.
+    #[must_use]
+    pub unsafe fn as_mut_slice_for(&self, range: std::ops::Range<usize>) -> UnsafeCellSliceGuard<'a, T> {
+        #[cfg(debug_assertions)]
+        {
+            let mut ranges = self.overlap_tracker.lock().unwrap();
+            assert!(
+                !ranges
+                    .iter()
+                    .any(|r| r.start < range.end && range.start < r.end),
+                "UnsafeCellSlice::as_mut_slice_for: range {range:?} overlaps an outstanding range in {ranges:?}"
+            );
+            ranges.push(range.clone());
+        }
+        let ptr = self.slice[range.start].get();
+        let slice = std::slice::from_raw_parts_mut(ptr, range.len());
+        UnsafeCellSliceGuard {
+            slice,
+            #[cfg(debug_assertions)]
+            range,
+            #[cfg(debug_assertions)]
+            overlap_tracker: self.overlap_tracker.clone(),
+        }
+    }
+}
+
+/// The sub-slice returned by [`as_mut_slice_for`](UnsafeCellSlice::as_mut_slice_for).
+///
+/// Derefs to `[T]`/`&mut [T]`; in debug builds, dropping it removes its range from the issuing
+/// [`UnsafeCellSlice`]'s overlap tracker, so the region it covered can be validly borrowed again by
+/// a later, non-overlapping call instead of being treated as permanently outstanding. In release
+/// builds this carries no tracker and drops as a no-op.
+#[must_use]
+pub struct UnsafeCellSliceGuard<'a, T> {
+    slice: &'a mut [T],
+    #[cfg(debug_assertions)]
+    range: std::ops::Range<usize>,
+    #[cfg(debug_assertions)]
+    overlap_tracker: std::sync::Arc<std::sync::Mutex<Vec<std::ops::Range<usize>>>>,
+}
+
+impl<'a, T> std::ops::Deref for UnsafeCellSliceGuard<'a, T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.slice
+    }
+}
+
+impl<'a, T> std::ops::DerefMut for UnsafeCellSliceGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.slice
+    }
+}
+
+#[cfg(debug_assertions)]
+impl<'a, T> Drop for UnsafeCellSliceGuard<'a, T> {
+    fn drop(&mut self) {
+        let mut ranges = self.overlap_tracker.lock().unwrap();
+        if let Some(pos) = ranges.iter().position(|r| *r == self.range) {
+            ranges.remove(pos);
+        }
     }
 }
 
@@ -99,3 +227,54 @@ unsafe fn vec_spare_capacity_to_mut_slice<T>(vec: &mut Vec<T>) -> &mut [T] {
         )
     }
 }
+
+impl<'a, T> UnsafeCellSlice<'a, std::mem::MaybeUninit<T>> {
+    /// Create a new [`UnsafeCellSlice`] over the spare capacity in a [`Vec`], without
+    /// reinterpreting the uninitialised bytes as `&mut [T]`.
+    ///
+    /// Unlike [`new_from_vec_with_spare_capacity`](UnsafeCellSlice::new_from_vec_with_spare_capacity),
+    /// this is sound for any `T`, including types with validity invariants (enums, `NonNull`,
+    /// references, `bool`, ...), because the spare capacity is exposed as `MaybeUninit<T>` rather
+    /// than `T` until it is explicitly [`write`](UnsafeCellSlice::write)-ed and
+    /// [`assume_init_len`](UnsafeCellSlice::assume_init_len)-ed.
+    #[must_use]
+    pub fn new_uninit_from_vec_with_spare_capacity(vec: &'a mut Vec<T>) -> Self {
+        let spare_capacity = vec.spare_capacity_mut();
+        let ptr = spare_capacity as *mut [std::mem::MaybeUninit<T>]
+            as *const [std::cell::UnsafeCell<std::mem::MaybeUninit<T>>];
+        Self::from_raw(unsafe { &*ptr })
+    }
+
+    /// Get a mutable reference to the underlying slice of [`MaybeUninit<T>`](std::mem::MaybeUninit).
+    ///
+    /// # Safety
+    /// This returns a mutable reference to the underlying slice despite `self` being a non-mutable reference.
+    /// This is unsafe because it can be called multiple times, thus creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to avoid data races and undefined behavior.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_mut_uninit_slice(&self) -> &mut [std::mem::MaybeUninit<T>] {
+        let ptr = self.slice[0].get();
+        std::slice::from_raw_parts_mut(ptr, self.slice.len())
+    }
+
+    /// Write `value` to the element at `index`.
+    ///
+    /// # Safety
+    /// `index` must be in bounds. It is the responsibility of the caller to ensure that no other
+    /// writer is writing to the same `index` concurrently.
+    pub unsafe fn write(&self, index: usize, value: T) {
+        (*self.slice[index].get()).write(value);
+    }
+
+    /// Mark the first `len` elements of `vec`'s spare capacity as initialised, extending `vec`'s
+    /// length accordingly.
+    ///
+    /// # Safety
+    /// Every index in `0..len` must have been initialised, e.g. via [`write`](UnsafeCellSlice::write),
+    /// before calling this.
+    pub unsafe fn assume_init_len(vec: &mut Vec<T>, len: usize) {
+        let new_len = vec.len() + len;
+        vec.set_len(new_len);
+    }
+}
@@ -1,5 +1,21 @@
 use extendr_api::prelude::*;
 
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ndarray::ShapeBuilder;
+use zarrs::array::codec::{BytesToBytesCodec, GzipCodec};
+use zarrs::array::{Array, ArrayBuilder, DataType, FillValue};
+use zarrs::array_subset::ArraySubset;
+use zarrs::filesystem::FilesystemStore; // requires filesystem feature
+use zarrs::storage::ReadableWritableListableStorage;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+mod snappy_codec;
+use snappy_codec::SnappyCodec;
+
 /// Return string `"Hello world!"` to R.
 /// @export
 #[extendr]
@@ -17,7 +33,6 @@ fn apic() -> &'static str {
 
 
 
-
 /// Return
 /// @export
 #[extendr]
@@ -83,8 +98,225 @@ let store_path: PathBuf = "/tmp/file24ddcc6d683865.zarr".into();
 "something new!!"
 }
 
+/// Convert any displayable error into an R-facing [`extendr_api::Error`].
+fn to_r_error<E: std::fmt::Display>(error: E) -> Error {
+    Error::from(error.to_string())
+}
+
+/// Open (or create, on first use) the filesystem store backing the Zarr hierarchy rooted at `path`.
+fn open_store(path: &str) -> Result<ReadableWritableListableStorage> {
+    let store_path: PathBuf = path.into();
+    Ok(Arc::new(
+        FilesystemStore::new(store_path).map_err(to_r_error)?,
+    ))
+}
+
+fn to_data_type(dtype: &str) -> Result<DataType> {
+    match dtype {
+        "float32" => Ok(DataType::Float32),
+        "float64" => Ok(DataType::Float64),
+        "int32" => Ok(DataType::Int32),
+        "int64" => Ok(DataType::Int64),
+        "uint8" => Ok(DataType::UInt8),
+        other => Err(format!("unsupported dtype: {other}").into()),
+    }
+}
+
+fn to_fill_value(dtype: &str) -> Result<FillValue> {
+    Ok(match dtype {
+        "float32" => FillValue::from(0f32),
+        "float64" => FillValue::from(0f64),
+        "int32" => FillValue::from(0i32),
+        "int64" => FillValue::from(0i64),
+        "uint8" => FillValue::from(0u8),
+        other => return Err(format!("unsupported dtype: {other}").into()),
+    })
+}
+
+fn to_bytes_to_bytes_codecs(codec: &str) -> Result<Vec<Arc<dyn BytesToBytesCodec>>> {
+    match codec {
+        "none" => Ok(vec![]),
+        "gzip" => Ok(vec![Arc::new(GzipCodec::new(5).map_err(to_r_error)?)]),
+        "snappy" => Ok(vec![Arc::new(SnappyCodec)]),
+        other => Err(format!("unsupported codec: {other}").into()),
+    }
+}
+
+/// The `dim` attribute of an R array/vector, falling back to a flat 1-D shape for a plain vector.
+fn r_dim(data: &Robj) -> Vec<u64> {
+    data.get_attrib(sym::dim_symbol())
+        .and_then(|dim| dim.as_integer_vector())
+        .map(|dim| dim.into_iter().map(|d| d as u64).collect())
+        .unwrap_or_else(|| vec![data.len() as u64])
+}
+
+/// An R numeric vector's elements as `f64`, accepting both `REALSXP` and `INTSXP` storage (a
+/// plain R integer vector/array is still "numeric" from the caller's point of view).
+fn r_numeric_vector(data: &Robj) -> Result<Vec<f64>> {
+    if let Some(values) = data.as_real_vector() {
+        return Ok(values);
+    }
+    if let Some(values) = data.as_integer_vector() {
+        return Ok(values.into_iter().map(|v| v as f64).collect());
+    }
+    Err(Error::from("data must be a numeric vector or array"))
+}
+
+/// Marshal an R numeric/integer vector or array into a (subset-shaped) [`ndarray::ArrayD`].
+fn ndarray_from_robj<T>(data: &Robj, convert: impl Fn(f64) -> T) -> Result<ndarray::ArrayD<T>> {
+    let shape: Vec<usize> = r_dim(data).into_iter().map(|d| d as usize).collect();
+    let values: Vec<T> = r_numeric_vector(data)?.into_iter().map(convert).collect();
+    // R stores array/vector data in column-major (Fortran) order; `.f()` tells ndarray the flat
+    // `values` are already laid out that way, rather than assuming the default C (row-major) order.
+    ndarray::ArrayD::from_shape_vec(ndarray::IxDyn(&shape).f(), values).map_err(to_r_error)
+}
 
+/// Marshal a retrieved [`ndarray::ArrayD`] back into an R array, preserving its shape as `dim`.
+fn ndarray_to_robj<T: Copy>(values: ndarray::ArrayD<T>, convert: impl Fn(T) -> f64) -> Robj {
+    let shape: Vec<i32> = values.shape().iter().map(|&d| d as i32).collect();
+    // `.iter()` always walks elements in logical (last-axis-fastest) order regardless of memory
+    // layout, so transpose first: the last axis of the reversed view is the first axis of
+    // `values`, making that traversal column-major (Fortran order) the way R expects.
+    let flat: Vec<f64> = values.t().iter().copied().map(convert).collect();
+    let mut robj = Robj::from(flat);
+    robj.set_attrib(sym::dim_symbol(), shape).unwrap();
+    robj
+}
 
+/// Create a new Zarr V3 array at `path` with the given `shape`/`chunk_shape`, a numeric `dtype`
+/// (`"float32"`, `"float64"`, `"int32"`, `"int64"` or `"uint8"`) and `codec` (`"none"`, `"gzip"` or
+/// `"snappy"`).
+/// @export
+#[extendr]
+fn zarr_create(
+    path: &str,
+    shape: Vec<i32>,
+    chunk_shape: Vec<i32>,
+    dtype: &str,
+    codec: &str,
+) -> Result<()> {
+    let store = open_store(path)?;
+    let shape: Vec<u64> = shape.into_iter().map(|v| v as u64).collect();
+    let chunk_shape: Vec<u64> = chunk_shape.into_iter().map(|v| v as u64).collect();
+    let array = ArrayBuilder::new(
+        shape,
+        to_data_type(dtype)?,
+        chunk_shape.try_into().map_err(to_r_error)?,
+        to_fill_value(dtype)?,
+    )
+    .bytes_to_bytes_codecs(to_bytes_to_bytes_codecs(codec)?)
+    .build(store, "/")
+    .map_err(to_r_error)?;
+    array.store_metadata().map_err(to_r_error)?;
+    Ok(())
+}
+
+/// Write `data` (an R numeric vector/array, with its `dim` attribute giving the subset shape)
+/// into the array at `path`, starting at `start`. `data` is marshalled to match the array's own
+/// `dtype` (the same `float32/float64/int32/int64/uint8` set [`zarr_create`] accepts), mirroring
+/// the dispatch [`zarr_read_subset`] does on the way out. The write is split across the chunks
+/// the subset overlaps and driven through [`iter_concurrent_limit!`] (`concurrency`, a chunk
+/// count; `0`/`NULL` means unlimited) so large fills don't have to hold every chunk's buffer at
+/// once.
+/// @export
+#[extendr]
+fn zarr_write_subset(path: &str, start: Vec<i32>, data: Robj, concurrency: Option<i32>) -> Result<()> {
+    let store = open_store(path)?;
+    let array = Array::open(store, "/").map_err(to_r_error)?;
+    let start: Vec<u64> = start.into_iter().map(|v| v as u64).collect();
+    let subset = ArraySubset::new_with_start_shape(start.clone(), r_dim(&data)).map_err(to_r_error)?;
+
+    let chunk_indices: Vec<Vec<u64>> = array
+        .chunks_in_array_subset(&subset)
+        .map_err(to_r_error)?
+        .map(|chunks| chunks.indices().into_iter().collect())
+        .unwrap_or_default();
+
+    let concurrent_limit = concurrency.map(|c| c.max(0) as usize).unwrap_or(0);
+
+    // Errors raised inside the closure below may run on rayon's thread pool (`concurrent_limit`
+    // of `0` or `>1` both go through a `ParallelIterator::try_for_each`), whose `R: Try` bound
+    // requires `R: Send`. `extendr_api::Error` wraps an `Robj`/`SEXP` and is not `Send`, so the
+    // closure collects `String` errors and only converts to an `Error` once back on this thread.
+    macro_rules! store_subset {
+        ($t:ty, $convert:expr) => {{
+            let values = ndarray_from_robj::<$t>(&data, $convert)?;
+            iter_concurrent_limit!(
+                concurrent_limit,
+                chunk_indices,
+                try_for_each,
+                |chunk_indices: Vec<u64>| -> std::result::Result<(), String> {
+                    let chunk_subset = array.chunk_subset(&chunk_indices).map_err(|e| e.to_string())?;
+                    let overlap = chunk_subset.overlap(&subset).map_err(|e| e.to_string())?;
+                    let local = overlap.relative_to(subset.start()).map_err(|e| e.to_string())?;
+                    let chunk_values = values.slice(local.to_ndarray_slice_info()).to_owned();
+                    let relative_to_chunk =
+                        overlap.relative_to(chunk_subset.start()).map_err(|e| e.to_string())?;
+                    array
+                        .store_chunk_subset_ndarray::<$t>(&chunk_indices, relative_to_chunk, chunk_values)
+                        .map_err(|e| e.to_string())
+                }
+            )
+            .map_err(to_r_error)
+        }};
+    }
+
+    match array.data_type() {
+        DataType::Float32 => store_subset!(f32, |v| v as f32),
+        DataType::Float64 => store_subset!(f64, |v| v),
+        DataType::Int32 => store_subset!(i32, |v| v as i32),
+        DataType::Int64 => store_subset!(i64, |v| v as i64),
+        DataType::UInt8 => store_subset!(u8, |v| v as u8),
+        other => Err(format!("unsupported dtype: {other:?}").into()),
+    }
+}
+
+/// Read the subset of the array at `path` starting at `start` with the given `shape`, returning
+/// an R array whose `dim` attribute matches `shape`.
+/// @export
+#[extendr]
+fn zarr_read_subset(path: &str, start: Vec<i32>, shape: Vec<i32>) -> Result<Robj> {
+    let store = open_store(path)?;
+    let array = Array::open(store, "/").map_err(to_r_error)?;
+    let start: Vec<u64> = start.into_iter().map(|v| v as u64).collect();
+    let shape: Vec<u64> = shape.into_iter().map(|v| v as u64).collect();
+    let subset = ArraySubset::new_with_start_shape(start, shape).map_err(to_r_error)?;
+
+    let robj = match array.data_type() {
+        DataType::Float32 => {
+            let values = array
+                .retrieve_array_subset_ndarray::<f32>(&subset)
+                .map_err(to_r_error)?;
+            ndarray_to_robj(values, |v| v as f64)
+        }
+        DataType::Float64 => {
+            let values = array
+                .retrieve_array_subset_ndarray::<f64>(&subset)
+                .map_err(to_r_error)?;
+            ndarray_to_robj(values, |v| v)
+        }
+        DataType::Int32 => {
+            let values = array
+                .retrieve_array_subset_ndarray::<i32>(&subset)
+                .map_err(to_r_error)?;
+            ndarray_to_robj(values, |v| v as f64)
+        }
+        DataType::Int64 => {
+            let values = array
+                .retrieve_array_subset_ndarray::<i64>(&subset)
+                .map_err(to_r_error)?;
+            ndarray_to_robj(values, |v| v as f64)
+        }
+        DataType::UInt8 => {
+            let values = array
+                .retrieve_array_subset_ndarray::<u8>(&subset)
+                .map_err(to_r_error)?;
+            ndarray_to_robj(values, |v| v as f64)
+        }
+        other => return Err(format!("unsupported dtype: {other:?}").into()),
+    };
+    Ok(robj)
+}
 
 // Macro to generate exports.
 // This ensures exported functions are registered with R.
@@ -94,4 +326,7 @@ extendr_module! {
     fn hello_world;
     fn apic;
     fn raex;
+    fn zarr_create;
+    fn zarr_write_subset;
+    fn zarr_read_subset;
 }
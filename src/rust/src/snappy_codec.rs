@@ -0,0 +1,71 @@
+//! A Zarr `bytes->bytes` codec built on the vendored raw [`snappy_src`] FFI bindings.
+//!
+//! Unlike [`snappy_src::compress_frame`]/[`snappy_src::decompress_frame`] (the framing/stream
+//! format, meant for arbitrary-length streams), a Zarr chunk is already a single bounded buffer,
+//! so [`SnappyCodec`] talks to the raw `snappy_compress`/`snappy_uncompress` bindings directly:
+//! one chunk in, one block out, no stream framing overhead.
+
+use snappy_src::{
+    snappy_compress, snappy_max_compressed_length, snappy_status_SNAPPY_OK, snappy_uncompress,
+    snappy_uncompressed_length,
+};
+
+use zarrs::array::codec::{BytesToBytesCodec, CodecError};
+
+/// Compresses/decompresses Zarr chunk bytes with raw (non-framed) Snappy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SnappyCodec;
+
+impl BytesToBytesCodec for SnappyCodec {
+    fn encode(&self, decoded_value: Vec<u8>) -> Result<Vec<u8>, CodecError> {
+        let bound = unsafe { snappy_max_compressed_length(decoded_value.len()) };
+        let mut encoded = Vec::<u8>::with_capacity(bound);
+        let mut encoded_len = bound;
+        let status = unsafe {
+            snappy_compress(
+                decoded_value.as_ptr().cast(),
+                decoded_value.len(),
+                encoded.as_mut_ptr().cast(),
+                &mut encoded_len,
+            )
+        };
+        if status != snappy_status_SNAPPY_OK {
+            return Err(CodecError::Other("snappy_compress failed".to_string()));
+        }
+        // SAFETY: `snappy_compress` reported success and wrote `encoded_len` <= `bound` bytes.
+        unsafe { encoded.set_len(encoded_len) };
+        Ok(encoded)
+    }
+
+    fn decode(&self, encoded_value: Vec<u8>) -> Result<Vec<u8>, CodecError> {
+        let mut decoded_len = 0usize;
+        let status = unsafe {
+            snappy_uncompressed_length(
+                encoded_value.as_ptr().cast(),
+                encoded_value.len(),
+                &mut decoded_len,
+            )
+        };
+        if status != snappy_status_SNAPPY_OK {
+            return Err(CodecError::Other(
+                "snappy_uncompressed_length failed".to_string(),
+            ));
+        }
+        let mut decoded = Vec::<u8>::with_capacity(decoded_len);
+        let mut out_len = decoded_len;
+        let status = unsafe {
+            snappy_uncompress(
+                encoded_value.as_ptr().cast(),
+                encoded_value.len(),
+                decoded.as_mut_ptr().cast(),
+                &mut out_len,
+            )
+        };
+        if status != snappy_status_SNAPPY_OK {
+            return Err(CodecError::Other("snappy_uncompress failed".to_string()));
+        }
+        // SAFETY: `snappy_uncompress` reported success and wrote `out_len` <= `decoded_len` bytes.
+        unsafe { decoded.set_len(out_len) };
+        Ok(decoded)
+    }
+}